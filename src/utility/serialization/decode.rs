@@ -1,4 +1,3 @@
-use std::marker::PhantomData;
 use byteorder::ReadBytesExt;
 use std::io::Read;
 use std::u32;
@@ -34,30 +33,310 @@ pub enum SizeLimit {
     Bounded(u64),
 }
 
+/// How integers are laid out on the wire.
+///
+/// The default, `Fixint`, writes every integer in its full fixed width, which
+/// keeps decoding branch-free but wastes space on the many small counts that
+/// show up in serialized scene and asset data. `Varint` encodes unsigned
+/// integers LEB128-style (seven bits per byte, low bits first, high bit set on
+/// every byte but the last) and signed integers with a zig-zag mapping applied
+/// first, so small-magnitude values — negative ones included — stay short.
+/// `u8` and `i8` are read as a single raw byte under either setting.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum IntEncoding {
+    Fixint,
+    Varint,
+}
+
+/// The byte order numbers are read in.
+///
+/// Carried as a value rather than a `byteorder::ByteOrder` type parameter so a
+/// single `Deserializer` can decode either layout — e.g. after reading an asset
+/// header that declares its own endianness — without a monomorphized copy per
+/// order.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Endian {
+    Little,
+    Big,
+    Native,
+}
+
+/// Tunables shared by the whole decode pass.
+///
+/// Mirrors bincode's `config` module: a single value threaded through the
+/// `Deserializer` so callers pick byte budget, integer layout and byte order up
+/// front instead of juggling type parameters.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Config {
+    pub limit: SizeLimit,
+    pub int_encoding: IntEncoding,
+    pub endian: Endian,
+}
+
+impl Config {
+    /// The historical defaults: unbounded little-endian reads with fixed-width
+    /// integers.
+    pub fn new() -> Config {
+        Config {
+            limit: SizeLimit::Infinite,
+            int_encoding: IntEncoding::Fixint,
+            endian: Endian::Little,
+        }
+    }
+
+    /// Selects the byte budget enforced while decoding.
+    pub fn limit(mut self, limit: SizeLimit) -> Config {
+        self.limit = limit;
+        self
+    }
+
+    /// Selects the integer layout expected on the wire.
+    pub fn int_encoding(mut self, int_encoding: IntEncoding) -> Config {
+        self.int_encoding = int_encoding;
+        self
+    }
+
+    /// Selects the byte order numbers are read in.
+    pub fn endian(mut self, endian: Endian) -> Config {
+        self.endian = endian;
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config::new()
+    }
+}
+
+/// An abstraction over the source a `Deserializer` pulls bytes from.
+///
+/// The plain `Read` interface always forces a copy into an owned buffer before
+/// a string or byte field can be handed to its visitor. When the input is an
+/// `&[u8]` that is already resident in memory — an asset blob loaded whole — a
+/// `BincodeRead` can point the visitor straight at the input slice and skip
+/// that intermediate `Vec`.
+///
+/// Two implementors are provided: `IoRead`, which wraps an arbitrary `Read` and
+/// buffers into an owned `Vec`, and `SliceReader`, which reads from a
+/// `&'de [u8]` without that copy.
+///
+/// NOTE: genuinely *borrowed* decoding — a `&'a str`/`&'a [u8]` field pointing
+/// straight into the input with no allocation at all — is not available here.
+/// It needs serde's `'de` lifetime and `Visitor::visit_borrowed_{str,bytes}`,
+/// which this crate's pinned serde predates (its `Visitor`/`Deserialize` carry
+/// no lifetime). So a `String`/`Vec` field still pays one `to_owned` copy in
+/// its visitor. Delivering the borrowed path is blocked on bumping serde to a
+/// release that exposes `Deserialize<'de>`.
+pub trait BincodeRead<'de>: Read {
+    /// Reads `len` bytes that encode a string, validates them as UTF-8 and
+    /// feeds the result to `visitor`.
+    fn read_str<V>(&mut self, len: usize, visitor: V) -> Result<V::Value>
+        where V: serde::de::Visitor;
+
+    /// Reads `len` raw bytes and feeds them to `visitor`.
+    fn read_bytes<V>(&mut self, len: usize, visitor: V) -> Result<V::Value>
+        where V: serde::de::Visitor;
+
+    /// Reports whether the input has been fully consumed, used by
+    /// `Deserializer::end` to reject trailing bytes. For a slice this is an
+    /// exact cursor comparison; for an arbitrary `Read` it costs one probing
+    /// read.
+    fn is_finished(&mut self) -> Result<bool>;
+}
+
+/// Undoes the zig-zag mapping applied to signed integers before LEB128.
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Maps a signed integer onto an unsigned one so small-magnitude values — of
+/// either sign — encode to few bytes. Inverse of `zigzag_decode`.
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Appends the LEB128 encoding of `value` to `out`, the byte layout the
+/// deserializer expects under `IntEncoding::Varint`.
+pub fn encode_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Appends the zig-zag + LEB128 encoding of a signed integer to `out`.
+pub fn encode_varint_signed(value: i64, out: &mut Vec<u8>) {
+    encode_varint(zigzag_encode(value), out);
+}
+
+fn varint_out_of_range() -> Error {
+    ErrorKind::InvalidEncoding {
+            desc: "varint value does not fit the target integer width",
+            detail: None,
+        }
+        .into()
+}
+
+fn invalid_utf8<E: ::std::fmt::Display>(err: E) -> Error {
+    ErrorKind::InvalidEncoding {
+            desc: "error while decoding utf8 string",
+            detail: Some(format!("Deserialize error: {}", err)),
+        }
+        .into()
+}
+
+/// A `BincodeRead` over any `Read`, preserving the original allocating
+/// behavior: strings and byte buffers are copied into an owned buffer before
+/// being handed to the visitor.
+pub struct IoRead<R> {
+    reader: R,
+}
+
+impl<R> IoRead<R>
+    where R: Read
+{
+    pub fn new(reader: R) -> IoRead<R> {
+        IoRead { reader: reader }
+    }
+
+    fn fill(&mut self, len: usize) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        self.reader.by_ref().take(len as u64).read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl<R> Read for IoRead<R>
+    where R: Read
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl<'de, R> BincodeRead<'de> for IoRead<R>
+    where R: Read
+{
+    fn read_str<V>(&mut self, len: usize, visitor: V) -> Result<V::Value>
+        where V: serde::de::Visitor
+    {
+        let buffer = self.fill(len)?;
+        let s = ::std::str::from_utf8(&buffer).map_err(invalid_utf8)?;
+        visitor.visit_str(s)
+    }
+
+    fn read_bytes<V>(&mut self, len: usize, visitor: V) -> Result<V::Value>
+        where V: serde::de::Visitor
+    {
+        let buffer = self.fill(len)?;
+        visitor.visit_bytes(&buffer)
+    }
+
+    fn is_finished(&mut self) -> Result<bool> {
+        let mut probe = [0u8; 1];
+        Ok(self.reader.read(&mut probe)? == 0)
+    }
+}
+
+/// A `BincodeRead` over an in-memory `&'de [u8]`. It keeps a cursor into the
+/// buffer and reads strings and byte blobs straight out of it, skipping the
+/// `read_to_end` copy that `IoRead` needs for an arbitrary `Read`.
+pub struct SliceReader<'de> {
+    slice: &'de [u8],
+    offset: usize,
+}
+
+impl<'de> SliceReader<'de> {
+    pub fn new(slice: &'de [u8]) -> SliceReader<'de> {
+        SliceReader {
+            slice: slice,
+            offset: 0,
+        }
+    }
+
+    /// Number of bytes still available in the buffer.
+    pub fn remaining(&self) -> usize {
+        self.slice.len() - self.offset
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        if len > self.remaining() {
+            return Err(ErrorKind::InvalidEncoding {
+                    desc: "not enough bytes remaining in slice",
+                    detail: None,
+                }
+                .into());
+        }
+
+        let from = self.offset;
+        self.offset += len;
+        Ok(&self.slice[from..self.offset])
+    }
+}
+
+impl<'de> Read for SliceReader<'de> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+        let len = ::std::cmp::min(buf.len(), self.remaining());
+        let from = self.offset;
+        buf[..len].copy_from_slice(&self.slice[from..from + len]);
+        self.offset += len;
+        Ok(len)
+    }
+}
+
+impl<'de> BincodeRead<'de> for SliceReader<'de> {
+    fn read_str<V>(&mut self, len: usize, visitor: V) -> Result<V::Value>
+        where V: serde::de::Visitor
+    {
+        let bytes = self.take(len)?;
+        let s = ::std::str::from_utf8(bytes).map_err(invalid_utf8)?;
+        visitor.visit_str(s)
+    }
+
+    fn read_bytes<V>(&mut self, len: usize, visitor: V) -> Result<V::Value>
+        where V: serde::de::Visitor
+    {
+        let bytes = self.take(len)?;
+        visitor.visit_bytes(bytes)
+    }
+
+    fn is_finished(&mut self) -> Result<bool> {
+        Ok(self.remaining() == 0)
+    }
+}
+
 /// A Deserializer that reads bytes from a buffer.
 ///
 /// This struct should rarely be used.
 /// In most cases, prefer the `decode_from` function.
-pub struct Deserializer<R, E>
-    where R: Read,
-          E: byteorder::ByteOrder
-{
+pub struct Deserializer<R> {
     reader: R,
-    size_limit: SizeLimit,
+    config: Config,
     read: u64,
-    _phantom: PhantomData<E>,
 }
 
-impl<R, E> Deserializer<R, E>
-    where R: Read,
-          E: byteorder::ByteOrder
+impl<'de, R> Deserializer<R>
+    where R: BincodeRead<'de>
 {
-    pub fn new(r: R, size_limit: SizeLimit) -> Deserializer<R, E> {
+    pub fn new(r: R, size_limit: SizeLimit) -> Deserializer<R> {
+        Deserializer::with_config(r, Config::new().limit(size_limit))
+    }
+
+    /// Builds a deserializer from a fully specified `Config`, allowing callers
+    /// to pick the integer encoding and byte order as well as the byte budget.
+    pub fn with_config(r: R, config: Config) -> Deserializer<R> {
         Deserializer {
             reader: r,
-            size_limit: size_limit,
+            config: config,
             read: 0,
-            _phantom: PhantomData,
         }
     }
 
@@ -66,6 +345,22 @@ impl<R, E> Deserializer<R, E>
         self.read
     }
 
+    /// Asserts that the whole input was consumed, failing with
+    /// `ErrorKind::TrailingBytes` if any bytes remain.
+    ///
+    /// Decoding a value only reads as much as that value needs, so a corrupt or
+    /// padded buffer can decode cleanly while leaving junk behind. Calling
+    /// `end` after a decode turns "this parsed" into "the buffer was exactly
+    /// this message". Framed streams that expect more messages to follow should
+    /// simply not call it.
+    pub fn end(mut self) -> Result<()> {
+        if self.reader.is_finished()? {
+            Ok(())
+        } else {
+            Err(ErrorKind::TrailingBytes.into())
+        }
+    }
+
     fn read_variant_uint(&mut self) -> Result<usize> {
         let v: u8 = serde::Deserialize::deserialize(&mut *self)?;
         if v < 0xFF {
@@ -78,32 +373,102 @@ impl<R, E> Deserializer<R, E>
 
     fn read_bytes(&mut self, count: u64) -> Result<()> {
         self.read += count;
-        match self.size_limit {
+        match self.config.limit {
             SizeLimit::Infinite => Ok(()),
             SizeLimit::Bounded(x) if self.read <= x => Ok(()),
             SizeLimit::Bounded(_) => Err(ErrorKind::SizeLimit.into()),
         }
     }
 
+    /// The byte budget still available before the size limit is hit, or `None`
+    /// when reads are unbounded.
+    fn remaining_budget(&self) -> Option<u64> {
+        match self.config.limit {
+            SizeLimit::Infinite => None,
+            SizeLimit::Bounded(x) => Some(x.saturating_sub(self.read)),
+        }
+    }
+
+    /// Rejects a collection whose announced length cannot possibly fit in the
+    /// remaining budget, *before* a visitor reserves capacity for it.
+    ///
+    /// Each element is charged at least one byte, so `len` bytes is taken as the
+    /// lower bound on what the collection will cost; if that alone overruns the
+    /// budget we fail early rather than letting a
+    /// `Vec`/`HashMap` try to allocate billions of slots up front. Elements that
+    /// decode from zero bytes, such as `()`, are charged one byte too, so a
+    /// collection of them longer than the remaining budget is also rejected.
+    fn guard_collection_len(&self, len: usize) -> Result<()> {
+        if let Some(budget) = self.remaining_budget() {
+            if len as u64 > budget {
+                return Err(ErrorKind::SizeLimit.into());
+            }
+        }
+        Ok(())
+    }
+
     fn read_type<T>(&mut self) -> Result<()> {
         use std::mem::size_of;
         self.read_bytes(size_of::<T>() as u64)
     }
 
-    fn read_str(&mut self) -> Result<String> {
-        let len = self.read_variant_uint()? as u64;
-        self.read_bytes(len)?;
-
-        let mut buffer = Vec::new();
-        self.reader.by_ref().take(len).read_to_end(&mut buffer)?;
-
-        String::from_utf8(buffer).map_err(|err| {
+    /// Decodes a LEB128 unsigned integer, charging each consumed byte against
+    /// the size limit as it is read.
+    ///
+    /// A `u64` needs at most ten 7-bit groups, so a buffer that keeps the
+    /// continuation bit set past `shift == 63` is malformed: rejecting it here
+    /// also keeps the shift below 64, where it would otherwise panic on
+    /// untrusted input.
+    ///
+    /// The tenth byte (`shift == 63`) only has room for a single value bit, so
+    /// anything other than `0` or `1` there — higher data bits *or* a
+    /// continuation bit — would be silently lost by the shift and is rejected.
+    fn read_varint(&mut self) -> Result<u64> {
+        let overflow = || -> Error {
             ErrorKind::InvalidEncoding {
-                    desc: "error while decoding utf8 string",
-                    detail: Some(format!("Deserialize error: {}", err)),
+                    desc: "varint overflows a 64-bit integer",
+                    detail: None,
                 }
                 .into()
-        })
+        };
+
+        let mut value: u64 = 0;
+        let mut shift: u32 = 0;
+        loop {
+            if shift >= 64 {
+                return Err(overflow());
+            }
+            self.read_bytes(1)?;
+            let byte = self.reader.read_u8()?;
+            if shift == 63 && byte > 1 {
+                return Err(overflow());
+            }
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Reads a length prefix and charges the byte budget for a string or byte
+    /// blob, returning the length so the reader can hand out the payload.
+    fn read_blob_len(&mut self) -> Result<usize> {
+        let len = self.read_variant_uint()? as u64;
+        self.read_bytes(len)?;
+        Ok(len as usize)
+    }
+}
+
+/// Reads a fixed-width number, picking the `byteorder` implementation from the
+/// configured `Endian` at the call site.
+macro_rules! read_fixint {
+    ($self:expr, $reader_method:ident) => {
+        match $self.config.endian {
+            Endian::Little => $self.reader.$reader_method::<byteorder::LittleEndian>()?,
+            Endian::Big => $self.reader.$reader_method::<byteorder::BigEndian>()?,
+            Endian::Native => $self.reader.$reader_method::<byteorder::NativeEndian>()?,
+        }
     }
 }
 
@@ -114,16 +479,65 @@ macro_rules! impl_nums {
             where V: serde::de::Visitor,
         {
             self.read_type::<$ty>()?;
-            let value = self.reader.$reader_method::<E>()?;
+            let value = read_fixint!(self, $reader_method);
             visitor.$visitor_method(value)
         }
     }
 }
 
+macro_rules! impl_unsigned {
+    ($ty:ty, $dser_method:ident, $visitor_method:ident, $reader_method:ident) => {
+        #[inline]
+        fn $dser_method<V>(self, visitor: V) -> Result<V::Value>
+            where V: serde::de::Visitor,
+        {
+            let value = match self.config.int_encoding {
+                IntEncoding::Fixint => {
+                    self.read_type::<$ty>()?;
+                    read_fixint!(self, $reader_method)
+                }
+                IntEncoding::Varint => {
+                    let wide = self.read_varint()?;
+                    let narrow = wide as $ty;
+                    if narrow as u64 != wide {
+                        return Err(varint_out_of_range());
+                    }
+                    narrow
+                }
+            };
+            visitor.$visitor_method(value)
+        }
+    }
+}
 
-impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
-    where R: Read,
-          E: byteorder::ByteOrder
+macro_rules! impl_signed {
+    ($ty:ty, $dser_method:ident, $visitor_method:ident, $reader_method:ident) => {
+        #[inline]
+        fn $dser_method<V>(self, visitor: V) -> Result<V::Value>
+            where V: serde::de::Visitor,
+        {
+            let value = match self.config.int_encoding {
+                IntEncoding::Fixint => {
+                    self.read_type::<$ty>()?;
+                    read_fixint!(self, $reader_method)
+                }
+                IntEncoding::Varint => {
+                    let wide = zigzag_decode(self.read_varint()?);
+                    let narrow = wide as $ty;
+                    if narrow as i64 != wide {
+                        return Err(varint_out_of_range());
+                    }
+                    narrow
+                }
+            };
+            visitor.$visitor_method(value)
+        }
+    }
+}
+
+
+impl<'de, 'a, R> serde::Deserializer for &'a mut Deserializer<R>
+    where R: BincodeRead<'de>
 {
     type Error = Error;
 
@@ -151,12 +565,12 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
         }
     }
 
-    impl_nums!(u16, deserialize_u16, visit_u16, read_u16);
-    impl_nums!(u32, deserialize_u32, visit_u32, read_u32);
-    impl_nums!(u64, deserialize_u64, visit_u64, read_u64);
-    impl_nums!(i16, deserialize_i16, visit_i16, read_i16);
-    impl_nums!(i32, deserialize_i32, visit_i32, read_i32);
-    impl_nums!(i64, deserialize_i64, visit_i64, read_i64);
+    impl_unsigned!(u16, deserialize_u16, visit_u16, read_u16);
+    impl_unsigned!(u32, deserialize_u32, visit_u32, read_u32);
+    impl_unsigned!(u64, deserialize_u64, visit_u64, read_u64);
+    impl_signed!(i16, deserialize_i16, visit_i16, read_i16);
+    impl_signed!(i32, deserialize_i32, visit_i32, read_i32);
+    impl_signed!(i64, deserialize_i64, visit_i64, read_i64);
     impl_nums!(f32, deserialize_f32, visit_f32, read_f32);
     impl_nums!(f64, deserialize_f64, visit_f64, read_f64);
 
@@ -230,25 +644,27 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        visitor.visit_str(&self.read_str()?)
+        let len = self.read_blob_len()?;
+        self.reader.read_str(len, visitor)
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        visitor.visit_string(self.read_str()?)
+        self.deserialize_str(visitor)
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        self.deserialize_seq(visitor)
+        let len = self.read_blob_len()?;
+        self.reader.read_bytes(len, visitor)
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        self.deserialize_seq(visitor)
+        self.deserialize_bytes(visitor)
     }
 
     fn deserialize_enum<V>(self,
@@ -258,9 +674,8 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
                            -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        impl<'a, R, E> serde::de::EnumVisitor for &'a mut Deserializer<R, E>
-            where R: 'a + Read,
-                  E: 'a + byteorder::ByteOrder
+        impl<'de, 'a, R> serde::de::EnumVisitor for &'a mut Deserializer<R>
+            where R: BincodeRead<'de>
         {
             type Error = Error;
             type Variant = Self;
@@ -280,13 +695,10 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
     fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        struct TupleVisitor<'a, R, E>(&'a mut Deserializer<R, E>)
-            where R: 'a + Read,
-                  E: 'a + byteorder::ByteOrder;
+        struct TupleVisitor<'a, R>(&'a mut Deserializer<R>) where R: 'a;
 
-        impl<'a, 'b: 'a, R, E> serde::de::SeqVisitor for TupleVisitor<'a, R, E>
-            where R: 'b + Read,
-                  E: byteorder::ByteOrder
+        impl<'de, 'a, 'b: 'a, R> serde::de::SeqVisitor for TupleVisitor<'a, R>
+            where R: BincodeRead<'de>
         {
             type Error = Error;
 
@@ -304,17 +716,15 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
     fn deserialize_seq_fixed_size<V>(self, len: usize, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        struct SeqVisitor<'a, R, E>
-            where R: 'a + Read,
-                  E: 'a + byteorder::ByteOrder
+        struct SeqVisitor<'a, R>
+            where R: 'a
         {
-            deserializer: &'a mut Deserializer<R, E>,
+            deserializer: &'a mut Deserializer<R>,
             len: usize,
         }
 
-        impl<'a, 'b: 'a, R, E> serde::de::SeqVisitor for SeqVisitor<'a, R, E>
-            where R: 'b + Read,
-                  E: byteorder::ByteOrder
+        impl<'de, 'a, 'b: 'a, R> serde::de::SeqVisitor for SeqVisitor<'a, R>
+            where R: BincodeRead<'de>
         {
             type Error = Error;
 
@@ -359,23 +769,22 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
         where V: serde::de::Visitor
     {
         let len = (&mut *self).read_variant_uint()?;
+        self.guard_collection_len(len)?;
         self.deserialize_seq_fixed_size(len, visitor)
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
         where V: serde::de::Visitor
     {
-        struct MapVisitor<'a, R, E>
-            where R: 'a + Read,
-                  E: 'a + byteorder::ByteOrder
+        struct MapVisitor<'a, R>
+            where R: 'a
         {
-            deserializer: &'a mut Deserializer<R, E>,
+            deserializer: &'a mut Deserializer<R>,
             len: usize,
         }
 
-        impl<'a, 'b: 'a, R, E> serde::de::MapVisitor for MapVisitor<'a, R, E>
-            where R: 'b + Read,
-                  E: byteorder::ByteOrder
+        impl<'de, 'a, 'b: 'a, R> serde::de::MapVisitor for MapVisitor<'a, R>
+            where R: BincodeRead<'de>
         {
             type Error = Error;
 
@@ -401,6 +810,7 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
         }
 
         let len = serde::Deserialize::deserialize(&mut *self)?;
+        self.guard_collection_len(len)?;
 
         visitor.visit_map(MapVisitor {
             deserializer: self,
@@ -455,9 +865,8 @@ impl<'a, R, E> serde::Deserializer for &'a mut Deserializer<R, E>
     }
 }
 
-impl<'a, R, E> serde::de::VariantVisitor for &'a mut Deserializer<R, E>
-    where R: Read,
-          E: byteorder::ByteOrder
+impl<'de, 'a, R> serde::de::VariantVisitor for &'a mut Deserializer<R>
+    where R: BincodeRead<'de>
 {
     type Error = Error;
 
@@ -484,6 +893,65 @@ impl<'a, R, E> serde::de::VariantVisitor for &'a mut Deserializer<R, E>
     }
 }
 
+/// Decodes a value of type `T` from any `Read`, buffering string and byte
+/// fields into owned storage, and verifies that the reader is exhausted
+/// afterwards (see `Deserializer::end`).
+pub fn decode_from<R, T>(reader: R, size_limit: SizeLimit, endian: Endian) -> Result<T>
+    where R: Read,
+          T: serde::Deserialize
+{
+    let config = Config::new().limit(size_limit).endian(endian);
+    let mut deserializer = Deserializer::with_config(IoRead::new(reader), config);
+    let value = serde::Deserialize::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Like `decode_from`, but tolerates unconsumed trailing bytes — for stream
+/// framing where more messages follow the one just decoded.
+pub fn decode_from_allow_trailing<R, T>(reader: R,
+                                        size_limit: SizeLimit,
+                                        endian: Endian)
+                                        -> Result<T>
+    where R: Read,
+          T: serde::Deserialize
+{
+    let config = Config::new().limit(size_limit).endian(endian);
+    let mut deserializer = Deserializer::with_config(IoRead::new(reader), config);
+    serde::Deserialize::deserialize(&mut deserializer)
+}
+
+/// Decodes a value of type `T` directly from an in-memory byte slice and
+/// verifies that the whole slice was consumed.
+///
+/// String and byte fields are read straight out of `slice` without the
+/// intermediate `Vec` that an arbitrary `Read` would need.
+pub fn decode_from_slice<'a, T>(slice: &'a [u8],
+                                size_limit: SizeLimit,
+                                endian: Endian)
+                                -> Result<T>
+    where T: serde::Deserialize
+{
+    let config = Config::new().limit(size_limit).endian(endian);
+    let mut deserializer = Deserializer::with_config(SliceReader::new(slice), config);
+    let value = serde::Deserialize::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Like `decode_from_slice`, but leaves any trailing bytes in the slice for a
+/// subsequent decode.
+pub fn decode_from_slice_allow_trailing<'a, T>(slice: &'a [u8],
+                                               size_limit: SizeLimit,
+                                               endian: Endian)
+                                               -> Result<T>
+    where T: serde::Deserialize
+{
+    let config = Config::new().limit(size_limit).endian(endian);
+    let mut deserializer = Deserializer::with_config(SliceReader::new(slice), config);
+    serde::Deserialize::deserialize(&mut deserializer)
+}
+
 static UTF8_CHAR_WIDTH: [u8; 256] =
     [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
      1 /* 0x1F */, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
@@ -498,4 +966,122 @@ static UTF8_CHAR_WIDTH: [u8; 256] =
 
 fn utf8_char_width(b: u8) -> usize {
     UTF8_CHAR_WIDTH[b as usize] as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::error::ErrorKind;
+
+    fn varint_config() -> Config {
+        Config::new().int_encoding(IntEncoding::Varint)
+    }
+
+    fn decode<T>(bytes: &[u8], config: Config) -> Result<T>
+        where T: serde::Deserialize
+    {
+        let mut de = Deserializer::with_config(SliceReader::new(bytes), config);
+        let value = serde::Deserialize::deserialize(&mut de)?;
+        de.end()?;
+        Ok(value)
+    }
+
+    #[test]
+    fn varint_roundtrips_unsigned_edges() {
+        for &n in &[0u64, 1, 127, 128, 300, ::std::u32::MAX as u64] {
+            let mut buffer = Vec::new();
+            encode_varint(n, &mut buffer);
+            let decoded: u32 = decode(&buffer, varint_config()).unwrap();
+            assert_eq!(decoded as u64, n);
+        }
+    }
+
+    #[test]
+    fn varint_roundtrips_signed_negatives_short() {
+        let mut buffer = Vec::new();
+        encode_varint_signed(-1, &mut buffer);
+        // -1 zig-zags to 1, a single byte.
+        assert_eq!(buffer.len(), 1);
+        let decoded: i32 = decode(&buffer, varint_config()).unwrap();
+        assert_eq!(decoded, -1);
+    }
+
+    #[test]
+    fn varint_rejects_value_wider_than_target() {
+        let mut buffer = Vec::new();
+        encode_varint(70_000, &mut buffer);
+        // 70_000 does not fit a u16: this must error rather than wrap to 4464.
+        let result: Result<u16> = decode(&buffer, varint_config());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn varint_rejects_overflowing_continuation() {
+        // Eleven continuation bytes would drive the shift past 63; the decoder
+        // must reject the buffer instead of panicking.
+        let buffer = [0xFFu8; 11];
+        let result: Result<u64> = decode(&buffer, varint_config());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn varint_rejects_high_bits_in_final_byte() {
+        // Nine continuation bytes carry no value, leaving the tenth byte at
+        // shift 63 where only bit 0 fits. A `2` there would be truncated to a
+        // wrong in-range u64, so it must be rejected.
+        let mut buffer = vec![0x80u8; 9];
+        buffer.push(0x02);
+        let result: Result<u64> = decode(&buffer, varint_config());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        // A u8 only consumes one byte; the second byte must be caught by end().
+        let bytes = [7u8, 99];
+        let result: Result<u8> = decode(&bytes, Config::new());
+        match result {
+            Err(ref err) => {
+                match *err.kind() {
+                    ErrorKind::TrailingBytes => {}
+                    ref other => panic!("expected TrailingBytes, got {:?}", other),
+                }
+            }
+            Ok(_) => panic!("expected an error"),
+        }
+
+        // The allow-trailing entry point tolerates the extra byte.
+        let value: u8 = decode_from_slice_allow_trailing(&bytes,
+                                                         SizeLimit::Infinite,
+                                                         Endian::Little)
+            .unwrap();
+        assert_eq!(value, 7);
+    }
+
+    #[test]
+    fn hostile_collection_length_is_rejected_before_allocation() {
+        // `0xFF` escapes to a u32 length of ~4 billion; with a tiny byte budget
+        // the guard must reject it before any Vec reserves capacity.
+        let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let config = Config::new().limit(SizeLimit::Bounded(16));
+        let result: Result<Vec<u8>> = decode(&bytes, config);
+        match result {
+            Err(ref err) => {
+                match *err.kind() {
+                    ErrorKind::SizeLimit => {}
+                    ref other => panic!("expected SizeLimit, got {:?}", other),
+                }
+            }
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn slice_string_roundtrips() {
+        // Length-prefixed UTF-8, read out of the slice (into an owned `String`,
+        // which still copies — see the note on `BincodeRead`).
+        let bytes = [2u8, b'h', b'i'];
+        let decoded: String = decode(&bytes, Config::new()).unwrap();
+        assert_eq!(decoded, "hi");
+    }
 }
\ No newline at end of file
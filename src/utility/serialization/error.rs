@@ -0,0 +1,90 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+use serde;
+
+/// The result of a (de)serialization operation in this module.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// Everything that can go wrong while decoding a bincode-style stream.
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// An underlying IO error surfaced from the reader.
+    Io(io::Error),
+    /// The bytes on the wire did not match the layout the decoder expected.
+    InvalidEncoding {
+        desc: &'static str,
+        detail: Option<String>,
+    },
+    /// The decode would have read past the configured `SizeLimit`.
+    SizeLimit,
+    /// A decode finished with bytes still left in the input (see
+    /// `Deserializer::end`).
+    TrailingBytes,
+    /// A message produced by `serde` itself, e.g. a failed `Deserialize` impl.
+    Custom(String),
+}
+
+/// The error type threaded through the decoder.
+#[derive(Debug)]
+pub struct Error(Box<ErrorKind>);
+
+impl Error {
+    /// The underlying cause of this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.0
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Error {
+        Error(Box::new(kind))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        ErrorKind::Io(err).into()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self.0 {
+            ErrorKind::Io(ref err) => write!(f, "{}", err),
+            ErrorKind::InvalidEncoding { desc, detail: Some(ref detail) } => {
+                write!(f, "{} ({})", desc, detail)
+            }
+            ErrorKind::InvalidEncoding { desc, detail: None } => write!(f, "{}", desc),
+            ErrorKind::SizeLimit => write!(f, "the size limit has been reached"),
+            ErrorKind::TrailingBytes => write!(f, "the input was not fully consumed"),
+            ErrorKind::Custom(ref msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self.0 {
+            ErrorKind::Io(ref err) => err.description(),
+            ErrorKind::InvalidEncoding { desc, .. } => desc,
+            ErrorKind::SizeLimit => "the size limit has been reached",
+            ErrorKind::TrailingBytes => "the input was not fully consumed",
+            ErrorKind::Custom(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&StdError> {
+        match *self.0 {
+            ErrorKind::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        ErrorKind::Custom(msg.to_string()).into()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod error;
+pub mod decode;
+
+pub use self::error::{Error, ErrorKind, Result};
+pub use self::decode::{Config, Deserializer, Endian, IntEncoding, SizeLimit};